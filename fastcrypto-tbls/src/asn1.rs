@@ -0,0 +1,220 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Minimal canonical DER/ASN.1 primitives used to give node public keys and [crate::nodes::Nodes]
+//! rosters a standards-based encoding, so that they can round-trip through external PKI/tooling
+//! instead of only this crate's internal `hash()`/bcs form. Only the handful of types actually
+//! needed here (`INTEGER`, `BIT STRING`, `OBJECT IDENTIFIER`, `SEQUENCE`) are implemented, and
+//! only the distinguished (canonical) encoding rules, since every consumer of `to_der`/`from_der`
+//! needs a unique, order-independent byte string for a given roster.
+
+use fastcrypto::error::{FastCryptoError, FastCryptoResult};
+use fastcrypto::groups::bls12381::G2Element;
+use fastcrypto::groups::ristretto255::RistrettoPoint;
+
+const TAG_INTEGER: u8 = 0x02;
+const TAG_BIT_STRING: u8 = 0x03;
+const TAG_OID: u8 = 0x06;
+const TAG_SEQUENCE: u8 = 0x30;
+
+/// Associates a group with the object identifier used to tag its DER-encoded keys and rosters,
+/// so a reader can tell which curve (e.g. BLS12-381 G2 vs Ristretto255) a byte string is for.
+/// The arcs live under this crate's private enterprise arc and are not registered externally.
+pub trait CurveOid {
+    /// Arc components of this group's OID, e.g. `&[1, 3, 6, 1, 4, 1, 167845, 1, 1]`.
+    const OID: &'static [u64];
+}
+
+impl CurveOid for G2Element {
+    const OID: &'static [u64] = &[1, 3, 6, 1, 4, 1, 167845, 1, 1];
+}
+
+impl CurveOid for RistrettoPoint {
+    const OID: &'static [u64] = &[1, 3, 6, 1, 4, 1, 167845, 1, 2];
+}
+
+/// Encode `len` using DER's definite-length rules (short form below 128, minimal long form
+/// otherwise).
+fn encode_length(len: usize) -> Vec<u8> {
+    if len < 0x80 {
+        return vec![len as u8];
+    }
+    let len_bytes = len.to_be_bytes();
+    let significant = &len_bytes[len_bytes.iter().position(|&b| b != 0).unwrap_or(len_bytes.len() - 1)..];
+    let mut out = vec![0x80 | significant.len() as u8];
+    out.extend_from_slice(significant);
+    out
+}
+
+fn encode_tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    out.extend(encode_length(content.len()));
+    out.extend_from_slice(content);
+    out
+}
+
+/// Encode a non-negative integer using the minimal number of big-endian bytes, with a leading
+/// `0x00` inserted if the most significant bit would otherwise be set (so it isn't misread as
+/// negative).
+pub(crate) fn encode_integer(value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let mut content = &bytes[bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len() - 1)..];
+    if content[0] & 0x80 != 0 {
+        let mut padded = vec![0u8];
+        padded.extend_from_slice(content);
+        return encode_tlv(TAG_INTEGER, &padded);
+    }
+    if content.is_empty() {
+        content = &[0];
+    }
+    encode_tlv(TAG_INTEGER, content)
+}
+
+/// Encode `bytes` as a `BIT STRING` with zero unused trailing bits.
+pub(crate) fn encode_bit_string(bytes: &[u8]) -> Vec<u8> {
+    let mut content = vec![0u8];
+    content.extend_from_slice(bytes);
+    encode_tlv(TAG_BIT_STRING, &content)
+}
+
+/// Encode an `OBJECT IDENTIFIER` from its arc components.
+pub(crate) fn encode_oid(arcs: &[u64]) -> Vec<u8> {
+    assert!(arcs.len() >= 2, "an OID needs at least two arcs");
+    let mut content = vec![(arcs[0] * 40 + arcs[1]) as u8];
+    for &arc in &arcs[2..] {
+        content.extend(encode_base128(arc));
+    }
+    encode_tlv(TAG_OID, &content)
+}
+
+fn encode_base128(mut value: u64) -> Vec<u8> {
+    let mut groups = vec![(value & 0x7f) as u8];
+    value >>= 7;
+    while value > 0 {
+        groups.push((value & 0x7f) as u8 | 0x80);
+        value >>= 7;
+    }
+    groups.reverse();
+    groups
+}
+
+/// Wrap the concatenation of already-encoded `parts` in a `SEQUENCE`.
+pub(crate) fn encode_sequence(parts: &[Vec<u8>]) -> Vec<u8> {
+    let content: Vec<u8> = parts.iter().flatten().copied().collect();
+    encode_tlv(TAG_SEQUENCE, &content)
+}
+
+/// A single decoded tag-length-value, with `rest` pointing past it.
+struct Tlv<'a> {
+    tag: u8,
+    content: &'a [u8],
+    rest: &'a [u8],
+}
+
+fn decode_tlv(data: &[u8]) -> FastCryptoResult<Tlv<'_>> {
+    let (&tag, data) = data.split_first().ok_or(FastCryptoError::InvalidInput)?;
+    let (&first_len_byte, data) = data.split_first().ok_or(FastCryptoError::InvalidInput)?;
+    let (len, data) = if first_len_byte & 0x80 == 0 {
+        (first_len_byte as usize, data)
+    } else {
+        let num_bytes = (first_len_byte & 0x7f) as usize;
+        if num_bytes == 0 || data.len() < num_bytes {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let (len_bytes, data) = data.split_at(num_bytes);
+        if num_bytes > 1 && len_bytes[0] == 0 {
+            // Redundant leading 0x00: the same length is representable with one fewer byte.
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let mut len = 0usize;
+        for &b in len_bytes {
+            len = len.checked_shl(8).ok_or(FastCryptoError::InvalidInput)? | b as usize;
+        }
+        if len < 0x80 {
+            // Should have used the short form for a length this small.
+            return Err(FastCryptoError::InvalidInput);
+        }
+        (len, data)
+    };
+    if data.len() < len {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let (content, rest) = data.split_at(len);
+    Ok(Tlv { tag, content, rest })
+}
+
+/// Decode a DER `INTEGER` into a `u64`, rejecting anything that isn't the unique canonical
+/// encoding of a non-negative value that fits: negative values (content's leading bit set with no
+/// padding byte), non-minimal encodings (a redundant leading `0x00`), and values wider than 64
+/// bits.
+pub(crate) fn decode_integer(data: &[u8]) -> FastCryptoResult<(u64, &[u8])> {
+    let tlv = decode_tlv(data)?;
+    let content = tlv.content;
+    if tlv.tag != TAG_INTEGER || content.is_empty() {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    if content[0] & 0x80 != 0 {
+        // Negative (or, without a padding byte, an unsigned value we can't represent as i64
+        // either way) — every integer this crate encodes is non-negative.
+        return Err(FastCryptoError::InvalidInput);
+    }
+    if content.len() > 1 && content[0] == 0 && content[1] & 0x80 == 0 {
+        // The leading 0x00 wasn't needed to keep the value non-negative, so this isn't the
+        // minimal (canonical) encoding.
+        return Err(FastCryptoError::InvalidInput);
+    }
+    if content.len() > 9 || (content.len() == 9 && content[0] != 0) {
+        // More than 8 significant bytes: doesn't fit in a u64.
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let mut value = 0u64;
+    for &b in content {
+        value = (value << 8) | b as u64;
+    }
+    Ok((value, tlv.rest))
+}
+
+pub(crate) fn decode_bit_string(data: &[u8]) -> FastCryptoResult<(&[u8], &[u8])> {
+    let tlv = decode_tlv(data)?;
+    if tlv.tag != TAG_BIT_STRING || tlv.content.first() != Some(&0) {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok((&tlv.content[1..], tlv.rest))
+}
+
+pub(crate) fn decode_oid(data: &[u8]) -> FastCryptoResult<(Vec<u64>, &[u8])> {
+    let tlv = decode_tlv(data)?;
+    if tlv.tag != TAG_OID || tlv.content.is_empty() {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    let mut arcs = vec![(tlv.content[0] / 40) as u64, (tlv.content[0] % 40) as u64];
+    let mut value = 0u64;
+    for &b in &tlv.content[1..] {
+        value = (value << 7) | (b & 0x7f) as u64;
+        if b & 0x80 == 0 {
+            arcs.push(value);
+            value = 0;
+        }
+    }
+    Ok((arcs, tlv.rest))
+}
+
+/// Decode a `SEQUENCE` and return its inner content (the concatenation of its elements' TLVs),
+/// which callers then parse element-by-element.
+pub(crate) fn decode_sequence(data: &[u8]) -> FastCryptoResult<(&[u8], &[u8])> {
+    let tlv = decode_tlv(data)?;
+    if tlv.tag != TAG_SEQUENCE {
+        return Err(FastCryptoError::InvalidInput);
+    }
+    Ok((tlv.content, tlv.rest))
+}
+
+/// Assert that `oid` (as decoded from a DER blob) matches the expected curve, mapping a mismatch
+/// onto the crate's usual error type.
+pub(crate) fn expect_oid<G: CurveOid>(oid: &[u64]) -> FastCryptoResult<()> {
+    if oid == G::OID {
+        Ok(())
+    } else {
+        Err(FastCryptoError::InvalidInput)
+    }
+}
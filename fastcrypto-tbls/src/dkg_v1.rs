@@ -0,0 +1,36 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The dealer side of a (weighted) DKG session: given a degree-`<t` secret polynomial and a
+//! roster, compute the secret share owed to every share id `1..=total_weight`.
+
+use crate::nodes::Nodes;
+use crate::polynomial::Poly;
+use fastcrypto::groups::{FiatShamirChallenge, GroupElement, Scalar};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::num::NonZeroU16;
+use zeroize::Zeroize;
+
+/// Compute the dealer's shares for every share id in `nodes`, i.e. `f(share_id)` for each
+/// `share_id` in `1..=nodes.total_weight()`.
+///
+/// Delegates to [Poly::eval_many], which uses the subproduct-tree batch path only when there are
+/// at least 32 share ids and `t > total_weight / 2`, and the naive per-point evaluation
+/// otherwise. For a typical weighted-DKG threshold (`t` a small fraction of `total_weight`, e.g.
+/// 100 nodes / `total_weight` 1361), that is the naive `O(total_weight * t)` loop, not the batch
+/// path; see [Poly::eval_many] for the cost of the batch path, whose polynomial multiplication and
+/// division are both schoolbook.
+pub fn deal_shares<G>(poly: &Poly<G::ScalarType>, nodes: &Nodes<G>) -> Vec<(NonZeroU16, G::ScalarType)>
+where
+    G: GroupElement + Serialize + DeserializeOwned,
+    G::ScalarType: FiatShamirChallenge + Zeroize,
+{
+    let share_ids: Vec<NonZeroU16> = nodes.share_ids_iter().collect();
+    let points: Vec<G::ScalarType> = share_ids
+        .iter()
+        .map(|id| G::ScalarType::from(id.get() as u64))
+        .collect();
+    let values = poly.eval_many(&points);
+    share_ids.into_iter().zip(values).collect()
+}
@@ -0,0 +1,90 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Key types for the ECIES scheme used to encrypt DKG messages sent between nodes: a private key
+//! is a scalar and the public key is `sk * G`. This module defines only the key pair, the
+//! derivation of a public key from a private key, and the DER encoding of public keys; it does
+//! not implement encryption or decryption.
+
+use crate::asn1::{self, CurveOid};
+use fastcrypto::error::{FastCryptoError, FastCryptoResult};
+use fastcrypto::groups::GroupElement;
+use rand::rngs::ThreadRng;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// An ECIES private key, i.e., a scalar in the group `G`. Zeroized on drop since it is the raw
+/// secret.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Debug, Zeroize, ZeroizeOnDrop)]
+pub struct PrivateKey<G: GroupElement>(G::ScalarType)
+where
+    G::ScalarType: Zeroize;
+
+/// An ECIES public key, i.e., `sk * G` for a private key `sk`.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct PublicKey<G: GroupElement>(G);
+
+impl<G: GroupElement> PrivateKey<G>
+where
+    G::ScalarType: Zeroize,
+{
+    /// Create a new random private key.
+    pub fn new(rng: &mut ThreadRng) -> Self {
+        Self(G::ScalarType::rand(rng))
+    }
+}
+
+impl<G: GroupElement> PublicKey<G>
+where
+    G: Serialize + DeserializeOwned,
+    G::ScalarType: Zeroize,
+{
+    /// Derive the public key that corresponds to a given private key.
+    pub fn from_private_key(sk: &PrivateKey<G>) -> Self {
+        Self(G::generator() * sk.0)
+    }
+}
+
+impl<G: GroupElement> PublicKey<G>
+where
+    G: Serialize + DeserializeOwned,
+{
+    /// The underlying group element of this public key.
+    pub fn as_element(&self) -> &G {
+        &self.0
+    }
+
+    /// Wrap an already-validated group element as a public key, e.g. when reconstructing one from
+    /// a roster's own DER encoding (see [crate::nodes::Nodes::from_der]).
+    pub(crate) fn from_element(element: G) -> Self {
+        Self(element)
+    }
+}
+
+impl<G: GroupElement> PublicKey<G>
+where
+    G: Serialize + DeserializeOwned + CurveOid,
+{
+    /// Canonically encode this public key as a DER `SEQUENCE { OBJECT IDENTIFIER, BIT STRING }`,
+    /// with the `OBJECT IDENTIFIER` identifying `G` so that external tooling can tell which curve
+    /// the key belongs to.
+    pub fn to_der(&self) -> Vec<u8> {
+        let element_bytes = bcs::to_bytes(&self.0).expect("serialization should not fail");
+        asn1::encode_sequence(&[
+            asn1::encode_oid(G::OID),
+            asn1::encode_bit_string(&element_bytes),
+        ])
+    }
+
+    /// Inverse of [PublicKey::to_der].
+    pub fn from_der(bytes: &[u8]) -> FastCryptoResult<Self> {
+        let (content, _) = asn1::decode_sequence(bytes)?;
+        let (oid, content) = asn1::decode_oid(content)?;
+        asn1::expect_oid::<G>(&oid)?;
+        let (element_bytes, _) = asn1::decode_bit_string(content)?;
+        let element: G =
+            bcs::from_bytes(element_bytes).map_err(|_| FastCryptoError::InvalidInput)?;
+        Ok(Self(element))
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+mod asn1;
+pub mod dkg_v1;
+pub mod ecies_v1;
+pub mod nodes;
+pub mod polynomial;
+
+#[cfg(test)]
+#[path = "tests/nodes_tests.rs"]
+mod nodes_tests;
+
+#[cfg(test)]
+#[path = "tests/polynomial_tests.rs"]
+mod polynomial_tests;
+
+#[cfg(test)]
+#[path = "tests/der_tests.rs"]
+mod der_tests;
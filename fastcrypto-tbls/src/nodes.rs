@@ -0,0 +1,339 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A [Nodes] instance is the roster of parties participating in a (possibly weighted) DKG/tBLS
+//! session: each [Node] has a stable id, an ECIES public key used to encrypt DKG messages to it,
+//! and a weight, i.e. the number of secret shares it is assigned. Shares are identified by a
+//! `NonZeroU16` in `1..=total_weight`, assigned consecutively to nodes in id order.
+
+use crate::asn1::{self, CurveOid};
+use crate::ecies_v1;
+use fastcrypto::error::{FastCryptoError, FastCryptoResult};
+use fastcrypto::groups::{FiatShamirChallenge, GroupElement};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::num::NonZeroU16;
+use zeroize::Zeroize;
+
+/// The maximum number of nodes supported by a single [Nodes] roster.
+const MAX_NUM_NODES: usize = 4000;
+
+/// A single participant in a (possibly weighted) committee.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, serde::Deserialize)]
+pub struct Node<G: GroupElement> {
+    pub id: u16,
+    pub pk: ecies_v1::PublicKey<G>,
+    pub weight: u16,
+}
+
+/// The roster of [Node]s participating in a session, along with the precomputed mapping from
+/// share ids to the node that owns them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Nodes<G: GroupElement> {
+    /// Sorted by id, ascending.
+    nodes: Vec<Node<G>>,
+    /// `starting_share_ids[i]` is the (1-indexed) share id of the first share owned by
+    /// `nodes[i]`, i.e. one more than the total weight of `nodes[..i]`; a zero-weight node gets
+    /// the same value as the node after it (or `total_weight + 1` if it is last). Used to binary
+    /// search from a share id to the owning node.
+    starting_share_ids: Vec<u16>,
+    total_weight: u16,
+}
+
+impl<G: GroupElement> Nodes<G>
+where
+    G: Serialize + DeserializeOwned,
+    G::ScalarType: FiatShamirChallenge + Zeroize,
+{
+    /// Create a new roster from a list of nodes. The ids must be exactly `0..nodes.len()` with no
+    /// gaps or duplicates (in any order), the total weight must be positive and must fit in a
+    /// `u16` (since share ids are `NonZeroU16`).
+    pub fn new(mut nodes: Vec<Node<G>>) -> FastCryptoResult<Self> {
+        if nodes.is_empty() || nodes.len() > MAX_NUM_NODES {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        nodes.sort_by_key(|n| n.id);
+        if nodes.iter().enumerate().any(|(i, n)| n.id != i as u16) {
+            return Err(FastCryptoError::InvalidInput);
+        }
+
+        let mut starting_share_ids = Vec::with_capacity(nodes.len());
+        let mut total_weight: u32 = 0;
+        for n in &nodes {
+            starting_share_ids.push((total_weight + 1) as u16);
+            total_weight += n.weight as u32;
+            if total_weight > u16::MAX as u32 {
+                return Err(FastCryptoError::InvalidInput);
+            }
+        }
+        if total_weight == 0 {
+            return Err(FastCryptoError::InvalidInput);
+        }
+
+        Ok(Self {
+            nodes,
+            starting_share_ids,
+            total_weight: total_weight as u16,
+        })
+    }
+
+    /// Reduce the weights of `nodes` by dividing each one by a common factor `d` (rounding down),
+    /// using the largest `d`, tried in increasing order up to the first that fails, for which the
+    /// weight lost to rounding (the sum of `weight % d` over all nodes) is at most `max_gap` and
+    /// the resulting total weight is at least `lower_bound`. Returns the reduced roster together
+    /// with `t` scaled by the same ratio as the total weight, rounded up.
+    ///
+    /// This lets a weighted DKG session bound the total number of shares dealt (and hence the
+    /// degree-`<t` polynomial evaluations required), at the cost of a small loss of precision in
+    /// the weight distribution.
+    pub fn new_reduced(
+        nodes: Vec<Node<G>>,
+        t: u16,
+        max_gap: u16,
+        lower_bound: u16,
+    ) -> FastCryptoResult<(Self, u16)> {
+        let original = Self::new(nodes.clone())?;
+        let total_weight = original.total_weight as u32;
+
+        let mut best: Option<(u32, Vec<Node<G>>, u16)> = None;
+        for d in 1..=total_weight {
+            let reduced: Vec<Node<G>> = nodes
+                .iter()
+                .map(|n| Node {
+                    id: n.id,
+                    pk: n.pk.clone(),
+                    weight: (n.weight as u32 / d) as u16,
+                })
+                .collect();
+            let new_total: u32 = reduced.iter().map(|n| n.weight as u32).sum();
+            let loss: u32 = nodes.iter().map(|n| n.weight as u32 % d).sum();
+            if loss > max_gap as u32 {
+                break;
+            }
+            if new_total < lower_bound as u32 {
+                break;
+            }
+            let new_t = ((t as u32 * new_total + total_weight - 1) / total_weight).max(1) as u16;
+            best = Some((new_total, reduced, new_t));
+        }
+
+        let (_, reduced, new_t) = best.ok_or(FastCryptoError::InvalidInput)?;
+        Ok((Self::new(reduced)?, new_t))
+    }
+
+    /// The nodes in this roster, sorted by id.
+    pub fn iter(&self) -> impl Iterator<Item = &Node<G>> {
+        self.nodes.iter()
+    }
+
+    /// The number of nodes in this roster.
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// The sum of the weights of all nodes in this roster.
+    pub fn total_weight(&self) -> u16 {
+        self.total_weight
+    }
+
+    /// All share ids assigned across this roster, i.e. `1..=total_weight`.
+    pub fn share_ids_iter(&self) -> impl Iterator<Item = NonZeroU16> {
+        (1..=self.total_weight).map(|i| NonZeroU16::new(i).expect("i is always > 0"))
+    }
+
+    /// The node with the given id.
+    pub fn node_id_to_node(&self, id: u16) -> FastCryptoResult<&Node<G>> {
+        self.nodes
+            .get(id as usize)
+            .filter(|n| n.id == id)
+            .ok_or(FastCryptoError::InvalidInput)
+    }
+
+    /// The node that owns the given share id.
+    pub fn share_id_to_node(&self, share_id: &NonZeroU16) -> FastCryptoResult<&Node<G>> {
+        let share_id = share_id.get();
+        if share_id > self.total_weight {
+            return Err(FastCryptoError::InvalidInput);
+        }
+        let idx = self
+            .starting_share_ids
+            .partition_point(|&start| start <= share_id)
+            .saturating_sub(1);
+        Ok(&self.nodes[idx])
+    }
+
+    /// All share ids owned by the node with the given id.
+    pub fn share_ids_of(&self, id: u16) -> FastCryptoResult<Vec<NonZeroU16>> {
+        let node = self.node_id_to_node(id)?;
+        let start = self.starting_share_ids[id as usize];
+        Ok((start..start + node.weight)
+            .map(|i| NonZeroU16::new(i).expect("i is always > 0"))
+            .collect())
+    }
+
+    /// A permutation-invariant hash of this roster, e.g. to be embedded in/compared against a
+    /// transcript between nodes that may have ordered their inputs differently.
+    pub fn hash(&self) -> [u8; 32] {
+        use fastcrypto::hash::{Blake2b256, HashFunction};
+        let mut hasher = Blake2b256::default();
+        // `self.nodes` is always kept sorted by id, so this is independent of the order the
+        // nodes were originally supplied in.
+        for node in &self.nodes {
+            hasher.update(bcs::to_bytes(node).expect("serialization should not fail"));
+        }
+        hasher.finalize().digest
+    }
+
+    /// Deterministically sample `k` distinct nodes from this roster without replacement, with
+    /// each node's probability of being drawn proportional to its weight (i.e. weight acts as
+    /// sampling stake). The result is a reproducible ordering derived solely from `seed`, so all
+    /// participants computing over the same roster and seed obtain the identical sequence.
+    ///
+    /// Sampling stops after `k` picks, or once every remaining node has weight zero, whichever
+    /// comes first. The whole computation is integer-only so that it is bit-for-bit reproducible
+    /// across platforms.
+    pub fn sample_by_weight(&self, seed: &[u8; 32], k: usize) -> Vec<&Node<G>> {
+        let mut rng = ChaCha20Rng::from_seed(*seed);
+        let mut tree = FenwickTree::new(self.nodes.iter().map(|n| n.weight as u64).collect());
+        let mut result = Vec::with_capacity(k.min(self.nodes.len()));
+
+        while result.len() < k && tree.total() > 0 {
+            let r = rng.gen_range(0..tree.total());
+            let idx = tree.find_by_prefix(r);
+            result.push(&self.nodes[idx]);
+            tree.zero_out(idx);
+        }
+        result
+    }
+
+    /// A deterministic, stake-weighted random ordering of this roster's nodes, derived from
+    /// `seed`. Equivalent to `self.sample_by_weight(seed, self.num_nodes())`.
+    ///
+    /// Zero-weight nodes can never be drawn (see [Nodes::sample_by_weight]), so they are omitted
+    /// from the result entirely rather than appearing at the end of the ordering — this is not a
+    /// permutation of all nodes when the roster has any. Callers doing e.g. committee rotation
+    /// over the result must account for zero-weight nodes separately if they need to appear.
+    pub fn weighted_shuffle(&self, seed: &[u8; 32]) -> Vec<&Node<G>> {
+        self.sample_by_weight(seed, self.nodes.len())
+    }
+}
+
+impl<G: GroupElement> Nodes<G>
+where
+    G: Serialize + DeserializeOwned + CurveOid,
+    G::ScalarType: FiatShamirChallenge + Zeroize,
+{
+    /// Canonically encode this roster as DER: a `SEQUENCE` of an `OBJECT IDENTIFIER` (identifying
+    /// `G`) followed by a `SEQUENCE OF` per-node `SEQUENCE { id INTEGER, weight INTEGER, pubkey
+    /// BIT STRING }`, in ascending id order. Since `self.nodes` is always kept sorted by id (see
+    /// [Nodes::new]), this is independent of the order the nodes were originally supplied in,
+    /// matching the permutation invariance of [Nodes::hash].
+    pub fn to_der(&self) -> Vec<u8> {
+        let entries: Vec<Vec<u8>> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let pk_bytes =
+                    bcs::to_bytes(node.pk.as_element()).expect("serialization should not fail");
+                asn1::encode_sequence(&[
+                    asn1::encode_integer(node.id as u64),
+                    asn1::encode_integer(node.weight as u64),
+                    asn1::encode_bit_string(&pk_bytes),
+                ])
+            })
+            .collect();
+        asn1::encode_sequence(&[asn1::encode_oid(G::OID), asn1::encode_sequence(&entries)])
+    }
+
+    /// Inverse of [Nodes::to_der]. Re-validates the decoded nodes through [Nodes::new], so an
+    /// encoding produced by a roster that was valid when created always decodes back to an equal
+    /// [Nodes].
+    pub fn from_der(bytes: &[u8]) -> FastCryptoResult<Self> {
+        let (content, _) = asn1::decode_sequence(bytes)?;
+        let (oid, content) = asn1::decode_oid(content)?;
+        asn1::expect_oid::<G>(&oid)?;
+        let (mut entries, _) = asn1::decode_sequence(content)?;
+
+        let mut nodes = Vec::new();
+        while !entries.is_empty() {
+            let (entry, rest) = asn1::decode_sequence(entries)?;
+            entries = rest;
+            let (id, entry) = asn1::decode_integer(entry)?;
+            let (weight, entry) = asn1::decode_integer(entry)?;
+            let (pk_bytes, _) = asn1::decode_bit_string(entry)?;
+            let element: G =
+                bcs::from_bytes(pk_bytes).map_err(|_| FastCryptoError::InvalidInput)?;
+            nodes.push(Node {
+                id: u16::try_from(id).map_err(|_| FastCryptoError::InvalidInput)?,
+                pk: ecies_v1::PublicKey::from_element(element),
+                weight: u16::try_from(weight).map_err(|_| FastCryptoError::InvalidInput)?,
+            });
+        }
+        Self::new(nodes)
+    }
+}
+
+/// A minimal Fenwick tree (binary indexed tree) over `u64` weights, supporting prefix-sum queries
+/// and point updates in `O(log n)`. Used to implement weighted sampling without replacement: the
+/// weight of a drawn element is zeroed out so it cannot be drawn again.
+struct FenwickTree {
+    /// 1-indexed internally; `tree[0]` is unused.
+    tree: Vec<u64>,
+    weights: Vec<u64>,
+    total: u64,
+}
+
+impl FenwickTree {
+    fn new(weights: Vec<u64>) -> Self {
+        let n = weights.len();
+        let mut tree = vec![0u64; n + 1];
+        for (i, &w) in weights.iter().enumerate() {
+            Self::add(&mut tree, i, w);
+        }
+        let total = weights.iter().sum();
+        Self {
+            tree,
+            weights,
+            total,
+        }
+    }
+
+    fn add(tree: &mut [u64], index: usize, delta: u64) {
+        let mut i = index + 1;
+        while i < tree.len() {
+            tree[i] = tree[i].wrapping_add(delta);
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// The smallest index `i` such that the sum of weights of `0..=i` is strictly greater than
+    /// `prefix`, i.e. the element whose cumulative weight interval contains `prefix`.
+    fn find_by_prefix(&self, prefix: u64) -> usize {
+        let mut remaining = prefix;
+        let mut pos = 0usize;
+        let mut step = self.tree.len().next_power_of_two() / 2;
+        while step > 0 {
+            let next = pos + step;
+            if next < self.tree.len() && self.tree[next] <= remaining {
+                remaining -= self.tree[next];
+                pos = next;
+            }
+            step /= 2;
+        }
+        pos
+    }
+
+    /// Set the weight at `index` to zero.
+    fn zero_out(&mut self, index: usize) {
+        let w = self.weights[index];
+        self.weights[index] = 0;
+        Self::add(&mut self.tree, index, w.wrapping_neg());
+        self.total -= w;
+    }
+}
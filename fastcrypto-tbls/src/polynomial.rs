@@ -0,0 +1,187 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Degree-`<t` polynomials over a group's scalar field, used by the dealer in a (weighted)
+//! verifiable secret sharing / DKG session to derive the per-share secrets handed out to parties.
+
+use fastcrypto::groups::Scalar;
+use rand::rngs::ThreadRng;
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Below this number of evaluation points, the subproduct-tree machinery in [Poly::eval_many]
+/// costs more than it saves regardless of degree, so we fall back to the naive `O(m * t)` loop.
+const BATCH_EVAL_THRESHOLD: usize = 32;
+
+/// A polynomial `f(X) = c_0 + c_1*X + ... + c_d*X^d` with coefficients in `S`, low-degree first.
+/// Holds the dealer's secret as its constant term (see [Poly::rand_fixed_secret]), so it zeroizes
+/// its coefficients on drop.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct Poly<S: Scalar + Zeroize>(Vec<S>);
+
+impl<S: Scalar + Zeroize> Poly<S> {
+    /// Create a polynomial from its coefficients, `coefficients[i]` being the coefficient of `X^i`.
+    pub fn new(coefficients: Vec<S>) -> Self {
+        Self(coefficients)
+    }
+
+    /// Sample a uniformly random polynomial of the given degree, with `c_0` (the secret) fixed to
+    /// `secret`.
+    pub fn rand_fixed_secret(degree: usize, secret: S, rng: &mut ThreadRng) -> Self {
+        let mut coefficients = Vec::with_capacity(degree + 1);
+        coefficients.push(secret);
+        coefficients.extend((0..degree).map(|_| S::rand(rng)));
+        Self(coefficients)
+    }
+
+    /// The degree of this polynomial, i.e. `t - 1` for a `t`-out-of-n threshold scheme.
+    pub fn degree(&self) -> usize {
+        self.0.len().saturating_sub(1)
+    }
+
+    /// Evaluate this polynomial at `x` using Horner's method, in `O(degree)`.
+    pub fn eval(&self, x: S) -> S {
+        self.0
+            .iter()
+            .rev()
+            .fold(S::zero(), |acc, c| acc * x + *c)
+    }
+
+    /// Evaluate this polynomial at every point in `xs`, one at a time. Costs `O(m * t)` for `m`
+    /// points and a degree-`<t` polynomial; kept around as the fallback for small `xs` and as a
+    /// correctness cross-check for [Poly::eval_many] in tests.
+    pub fn eval_many_naive(&self, xs: &[S]) -> Vec<S> {
+        xs.iter().map(|x| self.eval(*x)).collect()
+    }
+
+    /// Evaluate this polynomial at every point in `xs`, picking whichever of
+    /// [Poly::eval_many_naive] (`O(m * t)`) or the subproduct-tree path (below) is cheaper for the
+    /// given sizes, where `m = xs.len()` and `t = self.degree() + 1`.
+    ///
+    /// The subproduct-tree algorithm builds a balanced binary tree whose leaves are the linear
+    /// factors `(X - x_j)`, with each internal node holding the product of its children, then
+    /// evaluates top-down: reduce `f` modulo the root's product, recurse into the left subtree
+    /// with `f mod left_product` and the right with `f mod right_product`, until each leaf holds
+    /// `f(x_j)`. Both halves of that — building the product tree (`Poly::mul`) and reducing down
+    /// it (`Poly::rem`) — use schoolbook polynomial arithmetic (`O(deg(a) * deg(b))` per
+    /// multiplication/division, not a sub-quadratic FFT-based scheme), and each costs `O(m^2)` in
+    /// total across the tree *independent of `t`*: building is `m^2` worth of multiplications
+    /// irrespective of how small `t` is, and schoolbook-reducing `f` down the tree is likewise
+    /// `O(m^2)` since the per-node division cost is driven by the node's position in the tree, not
+    /// by `t`. So this path is never asymptotically better than the naive `O(m * t)` loop for any
+    /// `t < m` with this arithmetic — it is only a practical win, on constant factors, once `t` is
+    /// a sizeable fraction of `m` (empirically, roughly `t > m / 2`).
+    ///
+    /// The subproduct-tree path is therefore taken only when `m >= 32` and `t > m / 2`; in every
+    /// other case, including the usual weighted-DKG shape (`t` a small fraction of `m`, e.g. 100
+    /// nodes / `total_weight` 1361), this is the naive loop. The same holds for
+    /// [crate::dkg_v1::deal_shares], which calls this function.
+    pub fn eval_many(&self, xs: &[S]) -> Vec<S> {
+        let t = self.degree() + 1;
+        if xs.len() < BATCH_EVAL_THRESHOLD || t <= xs.len() / 2 {
+            return self.eval_many_naive(xs);
+        }
+        let tree = SubproductTree::build(xs);
+        let mut results = vec![S::zero(); xs.len()];
+        tree.eval_into(self, &mut results);
+        results
+    }
+}
+
+/// A node in the subproduct tree used by [Poly::eval_many]: a leaf stores the factor `(X - x_j)`
+/// for a single evaluation point, an internal node stores the product of its children's
+/// polynomials together with the index range of the points below it.
+enum SubproductTree<S: Scalar + Zeroize> {
+    Leaf {
+        /// `(X - x_j)`, i.e. `[-x_j, 1]`.
+        factor: Poly<S>,
+        index: usize,
+    },
+    Node {
+        product: Poly<S>,
+        left: Box<SubproductTree<S>>,
+        right: Box<SubproductTree<S>>,
+    },
+}
+
+impl<S: Scalar + Zeroize> SubproductTree<S> {
+    fn build(xs: &[S]) -> Self {
+        Self::build_range(xs, 0)
+    }
+
+    fn build_range(xs: &[S], offset: usize) -> Self {
+        if xs.len() == 1 {
+            return SubproductTree::Leaf {
+                factor: Poly::new(vec![S::zero() - xs[0], S::generator()]),
+                index: offset,
+            };
+        }
+        let mid = xs.len() / 2;
+        let left = Box::new(Self::build_range(&xs[..mid], offset));
+        let right = Box::new(Self::build_range(&xs[mid..], offset + mid));
+        let product = left.poly().mul(right.poly());
+        SubproductTree::Node {
+            product,
+            left,
+            right,
+        }
+    }
+
+    fn poly(&self) -> &Poly<S> {
+        match self {
+            SubproductTree::Leaf { factor, .. } => factor,
+            SubproductTree::Node { product, .. } => product,
+        }
+    }
+
+    /// Reduce `f` modulo this subtree's product and recurse, writing `f(x_j)` into
+    /// `results[index]` for every point `x_j` below this node.
+    fn eval_into(&self, f: &Poly<S>, results: &mut [S]) {
+        match self {
+            SubproductTree::Leaf { index, .. } => {
+                // f already reduced modulo (X - x_j), so it is the constant f(x_j).
+                results[*index] = f.0.first().copied().unwrap_or_else(S::zero);
+            }
+            SubproductTree::Node { left, right, .. } => {
+                let f_left = f.rem(left.poly());
+                let f_right = f.rem(right.poly());
+                left.eval_into(&f_left, results);
+                right.eval_into(&f_right, results);
+            }
+        }
+    }
+}
+
+impl<S: Scalar + Zeroize> Poly<S> {
+    /// Multiply two polynomials, in `O(deg(self) * deg(other))`.
+    fn mul(&self, other: &Poly<S>) -> Poly<S> {
+        if self.0.is_empty() || other.0.is_empty() {
+            return Poly::new(vec![]);
+        }
+        let mut result = vec![S::zero(); self.0.len() + other.0.len() - 1];
+        for (i, a) in self.0.iter().enumerate() {
+            for (j, b) in other.0.iter().enumerate() {
+                result[i + j] = result[i + j] + *a * *b;
+            }
+        }
+        Poly::new(result)
+    }
+
+    /// `self mod divisor`, via schoolbook polynomial long division. `divisor` must be monic
+    /// (leading coefficient `1`), which holds for every node of a [SubproductTree].
+    fn rem(&self, divisor: &Poly<S>) -> Poly<S> {
+        let mut remainder = self.0.clone();
+        let d = divisor.degree();
+        while remainder.len() > d {
+            let lead = *remainder.last().expect("non-empty");
+            if lead != S::zero() {
+                let shift = remainder.len() - 1 - d;
+                for (i, c) in divisor.0.iter().enumerate() {
+                    remainder[shift + i] = remainder[shift + i] - lead * *c;
+                }
+            }
+            remainder.pop();
+        }
+        Poly::new(remainder)
+    }
+}
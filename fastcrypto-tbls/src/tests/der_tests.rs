@@ -0,0 +1,123 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::asn1::CurveOid;
+use crate::ecies_v1;
+use crate::nodes::{Node, Nodes};
+use fastcrypto::groups::bls12381::G2Element;
+use fastcrypto::groups::ristretto255::RistrettoPoint;
+use fastcrypto::groups::{FiatShamirChallenge, GroupElement};
+use rand::prelude::SliceRandom;
+use rand::thread_rng;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use zeroize::Zeroize;
+
+fn get_nodes<G>(n: u16) -> Vec<Node<G>>
+where
+    G: GroupElement + Serialize + DeserializeOwned,
+    G::ScalarType: FiatShamirChallenge + Zeroize,
+{
+    let sk = ecies_v1::PrivateKey::<G>::new(&mut thread_rng());
+    let pk = ecies_v1::PublicKey::<G>::from_private_key(&sk);
+    (0..n)
+        .map(|i| Node {
+            id: i,
+            pk: pk.clone(),
+            weight: 1 + i % 7,
+        })
+        .collect()
+}
+
+fn test_public_key_round_trip<G>()
+where
+    G: GroupElement + Serialize + DeserializeOwned + CurveOid,
+    G::ScalarType: Zeroize,
+{
+    let sk = ecies_v1::PrivateKey::<G>::new(&mut thread_rng());
+    let pk = ecies_v1::PublicKey::<G>::from_private_key(&sk);
+    let der = pk.to_der();
+    assert_eq!(ecies_v1::PublicKey::<G>::from_der(&der).unwrap(), pk);
+}
+
+fn test_nodes_round_trip_and_order_invariance<G>()
+where
+    G: GroupElement + Serialize + DeserializeOwned + CurveOid,
+    G::ScalarType: FiatShamirChallenge + Zeroize,
+{
+    let mut nodes_vec = get_nodes::<G>(20);
+    let nodes = Nodes::new(nodes_vec.clone()).unwrap();
+    let der = nodes.to_der();
+    assert_eq!(Nodes::<G>::from_der(&der).unwrap(), nodes);
+
+    // DER encoding does not depend on the order nodes were supplied in.
+    nodes_vec.shuffle(&mut thread_rng());
+    let shuffled = Nodes::new(nodes_vec).unwrap();
+    assert_eq!(shuffled.to_der(), der);
+}
+
+fn test_nodes_from_der_rejects_other_curve<G, H>()
+where
+    G: GroupElement + Serialize + DeserializeOwned + CurveOid,
+    G::ScalarType: FiatShamirChallenge + Zeroize,
+    H: GroupElement + Serialize + DeserializeOwned + CurveOid,
+    H::ScalarType: FiatShamirChallenge + Zeroize,
+{
+    let nodes = Nodes::new(get_nodes::<G>(5)).unwrap();
+    let der = nodes.to_der();
+    assert!(Nodes::<H>::from_der(&der).is_err());
+}
+
+#[test]
+fn test_public_key_der_round_trip_bls12381_g2() {
+    test_public_key_round_trip::<G2Element>();
+}
+
+#[test]
+fn test_public_key_der_round_trip_ristretto255() {
+    test_public_key_round_trip::<RistrettoPoint>();
+}
+
+#[test]
+fn test_nodes_der_round_trip_bls12381_g2() {
+    test_nodes_round_trip_and_order_invariance::<G2Element>();
+}
+
+#[test]
+fn test_nodes_der_round_trip_ristretto255() {
+    test_nodes_round_trip_and_order_invariance::<RistrettoPoint>();
+}
+
+#[test]
+fn test_nodes_der_rejects_mismatched_curve() {
+    test_nodes_from_der_rejects_other_curve::<G2Element, RistrettoPoint>();
+    test_nodes_from_der_rejects_other_curve::<RistrettoPoint, G2Element>();
+}
+
+#[test]
+fn test_decode_integer_rejects_non_canonical() {
+    use crate::asn1::decode_integer;
+
+    // Canonical: a single content byte.
+    assert_eq!(decode_integer(&[0x02, 0x01, 0x05]).unwrap().0, 5);
+    // Canonical: padding byte required because the high bit of 0xFF is set.
+    assert_eq!(decode_integer(&[0x02, 0x02, 0x00, 0xFF]).unwrap().0, 0xFF);
+
+    // Non-canonical: redundant leading 0x00 (0x05 doesn't need padding).
+    assert!(decode_integer(&[0x02, 0x02, 0x00, 0x05]).is_err());
+    // Negative (high bit set, no padding byte).
+    assert!(decode_integer(&[0x02, 0x01, 0xFF]).is_err());
+    // Wider than a u64 (9 significant bytes, no padding byte).
+    assert!(decode_integer(&[0x02, 0x09, 0x01, 0, 0, 0, 0, 0, 0, 0, 0]).is_err());
+}
+
+#[test]
+fn test_decode_tlv_rejects_non_canonical_long_form_length() {
+    use crate::asn1::decode_integer;
+
+    // Non-canonical: long form used for a length (1) that fits in the short form.
+    assert!(decode_integer(&[0x02, 0x81, 0x01, 0x05]).is_err());
+    // Non-canonical: redundant leading 0x00 in a multi-byte long-form length (256 fits in 2
+    // length bytes, not 3).
+    assert!(decode_integer(&[0x02, 0x83, 0x00, 0x01, 0x00]).is_err());
+}
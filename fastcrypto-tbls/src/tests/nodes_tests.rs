@@ -7,7 +7,7 @@ use fastcrypto::groups::bls12381::G2Element;
 use fastcrypto::groups::ristretto255::RistrettoPoint;
 use fastcrypto::groups::{FiatShamirChallenge, GroupElement};
 use rand::prelude::SliceRandom;
-use rand::thread_rng;
+use rand::{thread_rng, Rng};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::num::NonZeroU16;
@@ -247,3 +247,71 @@ fn test_reduce_with_lower_bounds() {
     assert!(new_nodes2.total_weight() >= nodes.total_weight() / 3);
     assert!(new_nodes2.total_weight() < nodes.total_weight());
 }
+
+#[test]
+fn test_sample_by_weight_deterministic() {
+    let nodes_vec = get_nodes::<G2Element>(50);
+    let nodes = Nodes::new(nodes_vec).unwrap();
+    let seed = [7u8; 32];
+
+    let a: Vec<u16> = nodes
+        .sample_by_weight(&seed, 20)
+        .iter()
+        .map(|n| n.id)
+        .collect();
+    let b: Vec<u16> = nodes
+        .sample_by_weight(&seed, 20)
+        .iter()
+        .map(|n| n.id)
+        .collect();
+    assert_eq!(a, b);
+
+    // A different seed should (overwhelmingly likely) give a different order.
+    let c: Vec<u16> = nodes
+        .sample_by_weight(&[8u8; 32], 20)
+        .iter()
+        .map(|n| n.id)
+        .collect();
+    assert_ne!(a, c);
+}
+
+#[test]
+fn test_sample_by_weight_skips_zero_weight_nodes() {
+    let mut nodes_vec = get_nodes::<G2Element>(10);
+    for node in nodes_vec.iter_mut().take(5) {
+        node.weight = 0;
+    }
+    let nodes = Nodes::new(nodes_vec).unwrap();
+
+    // weighted_shuffle asks for all nodes, but only the 5 with nonzero weight can be drawn.
+    let shuffled = nodes.weighted_shuffle(&[1u8; 32]);
+    assert_eq!(shuffled.len(), 5);
+    assert!(shuffled.iter().all(|n| n.weight > 0));
+
+    // Asking sample_by_weight for more picks than there is nonzero weight also stops early
+    // instead of looping forever or drawing zero-weight nodes.
+    let sampled = nodes.sample_by_weight(&[1u8; 32], 10);
+    assert_eq!(sampled.len(), 5);
+}
+
+#[test]
+fn test_sample_by_weight_is_weight_biased() {
+    // One heavy node among many light ones should be drawn first far more often than chance.
+    let mut nodes_vec = get_nodes::<G2Element>(10);
+    for node in nodes_vec.iter_mut() {
+        node.weight = 1;
+    }
+    nodes_vec[5].weight = 1000;
+    let nodes = Nodes::new(nodes_vec).unwrap();
+
+    let trials = 200;
+    let mut heavy_drawn_first = 0;
+    for _ in 0..trials {
+        let seed: [u8; 32] = thread_rng().gen();
+        if nodes.sample_by_weight(&seed, 1)[0].id == 5 {
+            heavy_drawn_first += 1;
+        }
+    }
+    // Expected ~ 1000 / 1009 of draws; well above chance (1/10) even with generous slack.
+    assert!(heavy_drawn_first > trials * 9 / 10);
+}
@@ -0,0 +1,47 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::polynomial::Poly;
+use fastcrypto::groups::bls12381::Scalar as BlsScalar;
+use fastcrypto::groups::Scalar;
+use rand::thread_rng;
+
+fn random_poly(degree: usize) -> Poly<BlsScalar> {
+    Poly::new(
+        (0..=degree)
+            .map(|_| BlsScalar::rand(&mut thread_rng()))
+            .collect(),
+    )
+}
+
+#[test]
+fn test_eval_many_matches_naive_small() {
+    let poly = random_poly(10);
+    let points: Vec<BlsScalar> = (1..=20u64).map(BlsScalar::from).collect();
+    assert_eq!(poly.eval_many(&points), poly.eval_many_naive(&points));
+}
+
+#[test]
+fn test_eval_many_matches_naive_low_degree_large_roster() {
+    // A realistic weighted-DKG shape (t a small fraction of total_weight): eval_many should take
+    // the naive path here, not the subproduct tree (see eval_many's doc comment).
+    let poly = random_poly(50);
+    let points: Vec<BlsScalar> = (1..=1361u64).map(BlsScalar::from).collect();
+    assert_eq!(poly.eval_many(&points), poly.eval_many_naive(&points));
+}
+
+#[test]
+fn test_eval_many_matches_naive_high_degree() {
+    // t > m / 2: large enough, and high-enough degree relative to the number of points, to take
+    // the subproduct-tree path instead.
+    let poly = random_poly(900);
+    let points: Vec<BlsScalar> = (1..=1000u64).map(BlsScalar::from).collect();
+    assert_eq!(poly.eval_many(&points), poly.eval_many_naive(&points));
+}
+
+#[test]
+fn test_eval_single_point() {
+    let poly = random_poly(5);
+    let x = BlsScalar::rand(&mut thread_rng());
+    assert_eq!(poly.eval_many(&[x])[0], poly.eval(x));
+}